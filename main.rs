@@ -1,19 +1,94 @@
-use std::{fs, io::{BufRead, BufReader}, path::Path, process::{Child, Command, Stdio}, thread, time::{self, Duration}};
-use std::sync::{Arc, Mutex};
+use std::{fs, fs::{File, OpenOptions}, io::{Read, Write}, path::{Path, PathBuf}, process::{Child, ChildStderr, ChildStdout, Command, ExitStatus, Stdio}, thread, time::{self, Duration, SystemTime, UNIX_EPOCH}};
+use std::collections::{HashMap, HashSet};
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use serde::Serialize;
 use circular_buffer::CircularBuffer;
 use crossbeam_queue::SegQueue;
 use std::time::Instant;
 use pretty_duration::{pretty_duration, PrettyDurationOptions, PrettyDurationOutputFormat};
 
-const WORKING_DIR: &'static str = "/path/to/some/folder";
-const COMPLETED_DIR: &'static str = "/some/other/folder";
+const WORKING_DIR: &str = "/path/to/some/folder";
+const COMPLETED_DIR: &str = "/some/other/folder";
 const REPORT_INTERVAL: Duration = time::Duration::from_secs(10);
 const N_WORKERS: usize = 5; // how many cores you want to use for this
 
-const MXDYS_BB7_TM_EXECUTABLE: &'static str = "/path/to/mxdys/executable";
-const PYTHON_EXECUTABLE: &'static str = "python";
+// Kill any child that has run longer than this. mxdys and sligocki both
+// occasionally wedge; without a ceiling a single stuck process pins a worker
+// for the rest of the run.
+const TASK_TIMEOUT: Duration = time::Duration::from_secs(60 * 60);
+// How often the wait loop wakes up to poll the child and re-check the deadline.
+const POLL_INTERVAL: Duration = time::Duration::from_millis(100);
+
+// Per-task file logging. The small ring buffer still feeds the live status line;
+// these files capture the full output that the ring would otherwise drop.
+const ENABLE_FILE_LOGGING: bool = true;
+const LOG_DIR: &str = "logs";
+// Roll a task's log over once it passes this many bytes.
+const LOG_MAX_BYTES: u64 = 8 * 1024 * 1024;
+// Number of rotated generations to keep (`.1` .. `.LOG_KEEP`).
+const LOG_KEEP: u32 = 3;
+
+// Machine-readable status stream. Disabled by default; point it at a JSON-lines
+// file or a Unix socket for a dashboard/monitor to consume.
+const STATUS_SINK_CONFIG: StatusSinkConfig = StatusSinkConfig::Disabled;
+// How much of the stream to emit: `Quiet` = run-complete only, `Normal` = task
+// and stage transitions plus periodic snapshots, `Debug` = also every output line.
+const STATUS_VERBOSITY: Verbosity = Verbosity::Normal;
+
+/// Set by the SIGINT/SIGTERM handler; polled by the workers and the main loop so
+/// a Ctrl-C unwinds cleanly instead of orphaning children and losing progress.
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
+/// PIDs of children currently being waited on, so a shutdown can terminate them
+/// without having to share the owning `Child` handles across threads.
+static LIVE_CHILDREN: OnceLock<Mutex<HashSet<u32>>> = OnceLock::new();
+
+fn live_children() -> &'static Mutex<HashSet<u32>> {
+    LIVE_CHILDREN.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+extern "C" fn handle_shutdown_signal(_sig: libc::c_int) {
+    // Async-signal-safe: only a single atomic store.
+    SHUTDOWN.store(true, Ordering::SeqCst);
+}
+
+/// Install the SIGINT/SIGTERM handler that trips the shutdown flag.
+fn install_signal_handlers() {
+    // SAFETY: installing a handler that does nothing but an atomic store is
+    // async-signal-safe; the fn pointer outlives the program.
+    unsafe {
+        libc::signal(libc::SIGINT, handle_shutdown_signal as *const () as libc::sighandler_t);
+        libc::signal(libc::SIGTERM, handle_shutdown_signal as *const () as libc::sighandler_t);
+    }
+}
+
+/// Send SIGTERM to every child still registered as live.
+fn kill_live_children() {
+    let live = live_children().lock().unwrap();
+    for &pid in live.iter() {
+        // SAFETY: `kill` with a pid we spawned is safe; a stale pid just yields ESRCH.
+        unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM); }
+    }
+}
+
+/// Wall-clock budget for a given stage. The sligocki pass is driven by
+/// `--time=30` internally but can still run long on pathological inputs, so it
+/// gets a little more slack than the mxdys pass.
+fn task_timeout(kind: &TaskKind) -> Duration {
+    match kind {
+        TaskKind::MxdysEnum(_) => TASK_TIMEOUT,
+        TaskKind::SligockiEnum(_) => TASK_TIMEOUT + time::Duration::from_secs(30 * 60),
+        _ => TASK_TIMEOUT,
+    }
+}
+
+const MXDYS_BB7_TM_EXECUTABLE: &str = "/path/to/mxdys/executable";
+const PYTHON_EXECUTABLE: &str = "python";
 const PYENV_VIRTUALENV: Option<&'static str> = None; // likely not relevant unless you have a pyenv virtual environment like I do
-const ENUMERATE_PY_PATH: &'static str = "/something/something/Code/Enumerate.py";
+const ENUMERATE_PY_PATH: &str = "/something/something/Code/Enumerate.py";
 
 fn main() {
     let task_ids = SegQueue::new();
@@ -21,19 +96,36 @@ fn main() {
         task_ids.push(i);
     }
 
+    install_signal_handlers();
+
     let start_time = Instant::now();
-    let worker_group = WorkerGroup::new(N_WORKERS, task_ids);
+    let mut worker_group = WorkerGroup::new(N_WORKERS, task_ids);
+    let mut shutting_down = false;
 
     loop {
+        if SHUTDOWN.load(Ordering::SeqCst) && !shutting_down {
+            shutting_down = true;
+            println!("interrupt received, shutting down: {} tasks completed so far.", worker_group.completed_count());
+            worker_group.initiate_shutdown();
+        }
         if worker_group.is_done() {
+            let outcomes = worker_group.outcomes();
+            let completed = worker_group.completed_count();
             worker_group.join_all();
             let duration_s = pretty_duration(&start_time.elapsed(), Some(COMPACT_OPTIONS));
-            println!("All done, {duration_s}.");
-            io::stdout().flush().expect("failed to flush stdout");
-            break;
+            if shutting_down {
+                println!("Interrupted after {duration_s}, {completed} tasks completed.");
+            } else {
+                println!("All done, {duration_s}.");
+            }
+            let worst = merge_exitcodes(&outcomes);
+            std::io::stdout().flush().expect("failed to flush stdout");
+            std::process::exit(worst);
         }
         worker_group.print_status();
-        thread::sleep(REPORT_INTERVAL);
+        worker_group.emit_snapshot();
+        // Poll more eagerly while draining so Ctrl-C feels responsive.
+        thread::sleep(if shutting_down { POLL_INTERVAL } else { REPORT_INTERVAL });
     }
 }
 
@@ -43,6 +135,155 @@ const COMPACT_OPTIONS: PrettyDurationOptions = PrettyDurationOptions {
     plural_labels: None,
 };
 
+/// A task currently checked out by a worker. Persisted so that a crash leaves a
+/// record we can notice and re-queue on the next launch.
+struct ActiveEntry {
+    worker_id: usize,
+    pid: u32,
+    start_epoch: u64,
+}
+
+/// On-disk record of task outcomes, kept in three files under `WORKING_DIR`
+/// (`tasks.active`, `tasks.completed`, `tasks.failed`). Every mutation rewrites
+/// the affected file via a temp-file + `rename` so a crash mid-write can never
+/// leave a half-written registry behind. The whole thing is meant to live behind
+/// a single `Mutex` shared by all workers.
+struct TaskRegistry {
+    active: HashMap<u32, ActiveEntry>,
+    completed: HashSet<u32>,
+    failed: HashSet<u32>,
+}
+
+impl TaskRegistry {
+    fn active_path() -> PathBuf { Path::new(WORKING_DIR).join("tasks.active") }
+    fn completed_path() -> PathBuf { Path::new(WORKING_DIR).join("tasks.completed") }
+    fn failed_path() -> PathBuf { Path::new(WORKING_DIR).join("tasks.failed") }
+
+    /// Load the registry from disk, tolerating missing files (a fresh run).
+    fn load() -> Self {
+        let active = read_active(&Self::active_path());
+        let completed = read_ids(&Self::completed_path());
+        let failed = read_ids(&Self::failed_path());
+        TaskRegistry { active, completed, failed }
+    }
+
+    /// Task ids that finished successfully on a previous run and should not be
+    /// enumerated again.
+    fn completed_ids(&self) -> &HashSet<u32> {
+        &self.completed
+    }
+
+    /// Ids left dangling in `active` by a crashed run; these need re-queuing.
+    fn dangling_ids(&self) -> Vec<u32> {
+        self.active.keys().copied().collect()
+    }
+
+    fn mark_active(&mut self, task_id: u32, worker_id: usize, pid: u32) {
+        self.active.insert(task_id, ActiveEntry {
+            worker_id,
+            pid,
+            start_epoch: now_epoch(),
+        });
+        self.flush_active();
+    }
+
+    fn mark_completed(&mut self, task_id: u32) {
+        self.active.remove(&task_id);
+        self.completed.insert(task_id);
+        self.flush_active();
+        atomic_write(&Self::completed_path(), &ids_to_string(&self.completed));
+    }
+
+    fn mark_failed(&mut self, task_id: u32) {
+        self.active.remove(&task_id);
+        self.failed.insert(task_id);
+        self.flush_active();
+        atomic_write(&Self::failed_path(), &ids_to_string(&self.failed));
+    }
+
+    /// Drop a task back to pending (neither completed nor failed) so the next
+    /// launch picks it up again from the seed list. Used on interrupted runs.
+    fn mark_pending(&mut self, task_id: u32) {
+        self.active.remove(&task_id);
+        self.flush_active();
+    }
+
+    fn completed_count(&self) -> usize {
+        self.completed.len()
+    }
+
+    fn flush_active(&self) {
+        let mut out = String::new();
+        for (id, e) in &self.active {
+            out.push_str(&format!("{} {} {} {}\n", id, e.worker_id, e.pid, e.start_epoch));
+        }
+        atomic_write(&Self::active_path(), &out);
+    }
+}
+
+fn now_epoch() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Write `contents` to `path` atomically: fill a sibling temp file first, then
+/// `rename` it over the target (rename is atomic on the same filesystem).
+fn atomic_write(path: &Path, contents: &str) {
+    // Append `.tmp` to the full filename rather than replacing the extension, so
+    // each registry file gets its own temp path (`tasks.active` ->
+    // `tasks.active.tmp`) instead of all three colliding on `tasks.tmp`.
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    if let Err(e) = fs::write(&tmp, contents) {
+        eprintln!("failed to write {tmp:?}: {e}");
+        return;
+    }
+    if let Err(e) = fs::rename(&tmp, path) {
+        eprintln!("failed to replace {path:?}: {e}");
+    }
+}
+
+fn ids_to_string(ids: &HashSet<u32>) -> String {
+    let mut v: Vec<u32> = ids.iter().copied().collect();
+    v.sort_unstable();
+    let mut out = String::new();
+    for id in v {
+        out.push_str(&format!("{id}\n"));
+    }
+    out
+}
+
+fn read_ids(path: &Path) -> HashSet<u32> {
+    let mut set = HashSet::new();
+    if let Ok(contents) = fs::read_to_string(path) {
+        for line in contents.lines() {
+            if let Ok(id) = line.trim().parse::<u32>() {
+                set.insert(id);
+            }
+        }
+    }
+    set
+}
+
+fn read_active(path: &Path) -> HashMap<u32, ActiveEntry> {
+    let mut map = HashMap::new();
+    if let Ok(contents) = fs::read_to_string(path) {
+        for line in contents.lines() {
+            let mut parts = line.split_whitespace();
+            let id = parts.next().and_then(|s| s.parse::<u32>().ok());
+            let worker_id = parts.next().and_then(|s| s.parse::<usize>().ok());
+            let pid = parts.next().and_then(|s| s.parse::<u32>().ok());
+            let start_epoch = parts.next().and_then(|s| s.parse::<u64>().ok());
+            if let (Some(id), Some(worker_id), Some(pid), Some(start_epoch)) =
+                (id, worker_id, pid, start_epoch)
+            {
+                map.insert(id, ActiveEntry { worker_id, pid, start_epoch });
+            }
+        }
+    }
+    map
+}
+
 struct Worker {
     id: usize,
     thread: thread::JoinHandle<()>,
@@ -50,14 +291,19 @@ struct Worker {
 }
 
 impl Worker {
-    fn new(id: usize, job_queue: Arc<SegQueue<u32>>) -> Worker {
+    fn new(id: usize, job_queue: Arc<SegQueue<u32>>, registry: Arc<Mutex<TaskRegistry>>, outcomes: Arc<SegQueue<TaskOutcome>>) -> Worker {
         let task_info = Arc::new(Mutex::new(TaskInfo::new()));
         let info1 = task_info.clone();
 
         let thread = thread::spawn(move || loop {
+            if SHUTDOWN.load(Ordering::SeqCst) {
+                let mut info_inner = info1.lock().unwrap();
+                info_inner.kind = TaskKind::Done;
+                break;
+            }
             match job_queue.pop() {
                 Some(task_id) => {
-                    combined_enumeration(task_id, Arc::clone(&info1));
+                    combined_enumeration(task_id, id, Arc::clone(&info1), Arc::clone(&registry), Arc::clone(&outcomes));
                 }
                 None => {
                     let mut info_inner = info1.lock().unwrap();
@@ -74,16 +320,47 @@ impl Worker {
 struct WorkerGroup {
     workers: Vec<Worker>,
     job_queue: Arc<SegQueue<u32>>,
+    registry: Arc<Mutex<TaskRegistry>>,
+    outcomes: Arc<SegQueue<TaskOutcome>>,
 }
 
 impl WorkerGroup {
     fn new(size: usize, task_ids: SegQueue<u32>) -> WorkerGroup {
-        let job_queue = Arc::new(task_ids);
+        let registry = Arc::new(Mutex::new(TaskRegistry::load()));
+
+        // Rebuild the queue from the requested ids, skipping anything already
+        // completed on a previous run and re-queuing anything a crash left
+        // dangling in `active`.
+        let job_queue = Arc::new(SegQueue::new());
+        {
+            let reg = registry.lock().unwrap();
+            let completed = reg.completed_ids();
+            let mut enqueued = HashSet::new();
+            while let Some(task_id) = task_ids.pop() {
+                if !completed.contains(&task_id) && enqueued.insert(task_id) {
+                    job_queue.push(task_id);
+                }
+            }
+            // Re-queue crashed tasks, but only those the seed didn't already cover
+            // so a resume never runs the same id twice.
+            for task_id in reg.dangling_ids() {
+                if !completed.contains(&task_id) && enqueued.insert(task_id) {
+                    job_queue.push(task_id);
+                }
+            }
+        }
+
+        let outcomes = Arc::new(SegQueue::new());
         let mut workers = Vec::with_capacity(size);
         for id in 0..size {
-            workers.push(Worker::new(id, Arc::clone(&job_queue)));
+            workers.push(Worker::new(id, Arc::clone(&job_queue), Arc::clone(&registry), Arc::clone(&outcomes)));
         }
-        WorkerGroup { workers, job_queue }
+        WorkerGroup { workers, job_queue, registry, outcomes }
+    }
+
+    /// Shared handle to the outcome accumulator, for building the final report.
+    fn outcomes(&self) -> Arc<SegQueue<TaskOutcome>> {
+        Arc::clone(&self.outcomes)
     }
 
     fn _clear_pending_jobs(&mut self) {
@@ -92,6 +369,18 @@ impl WorkerGroup {
         }
     }
 
+    /// React to a shutdown request: drain the queue so no new tasks start, then
+    /// SIGTERM every child still running so workers unwind promptly.
+    fn initiate_shutdown(&mut self) {
+        self._clear_pending_jobs();
+        kill_live_children();
+    }
+
+    /// How many tasks the persistent registry has recorded as completed.
+    fn completed_count(&self) -> usize {
+        self.registry.lock().unwrap().completed_count()
+    }
+
     fn print_status(&self) {
         let mut status_start = ">"; // visually indicate the first line of the status report
         for w in &self.workers {
@@ -105,6 +394,24 @@ impl WorkerGroup {
         }
     }
 
+    /// Emit a snapshot of every busy worker for external monitors.
+    fn emit_snapshot(&self) {
+        let mut workers = Vec::new();
+        for w in &self.workers {
+            let info = w.task_info.lock().unwrap();
+            if info.kind == TaskKind::Done {
+                continue;
+            }
+            workers.push(WorkerSnapshot {
+                worker_id: w.id,
+                kind: format!("{:?}", info.kind),
+                elapsed_secs: info.start_time.elapsed().as_secs_f64(),
+                last_line: info.out_buf.back().cloned().unwrap_or_default(),
+            });
+        }
+        emit_status(StatusEvent::Snapshot { workers });
+    }
+
     fn is_done(&self) -> bool {
         for w in &self.workers {
             let info2 = w.task_info.lock().unwrap();
@@ -129,6 +436,7 @@ enum TaskKind {
     MxdysEnum(u32),
     SligockiEnum(u32),
     MoveFile,
+    TimedOut,
     Done
 }
 
@@ -153,10 +461,17 @@ impl TaskInfo {
 pub struct ProcessWithBuffer {
     child: Child,
     info: Arc<Mutex<TaskInfo>>,
+    stdout: Option<ChildStdout>,
+    stderr: Option<ChildStderr>,
+    out_acc: Vec<u8>,
+    err_acc: Vec<u8>,
+    logger: Option<Arc<Mutex<TaskLogger>>>,
+    stage: &'static str,
+    task_id: u32,
 }
 
 impl ProcessWithBuffer {
-    pub fn new(command: &mut Command, info: Arc<Mutex<TaskInfo>>) -> std::io::Result<Self>  {
+    pub fn new(command: &mut Command, info: Arc<Mutex<TaskInfo>>, logger: Option<Arc<Mutex<TaskLogger>>>, stage: &'static str, task_id: u32) -> std::io::Result<Self>  {
         {
             let mut info_guard = info.lock().unwrap();
             info_guard.start_time = Instant::now();
@@ -178,32 +493,244 @@ impl ProcessWithBuffer {
             .take()
             .expect("Accessing stdout should never fail after passing Stdio::piped().");
 
-        let info1 = info.clone();
-        thread::spawn(move || {
-            for line in BufReader::new(stdout).lines() {
-                let mut info = info1.lock().unwrap();
-                match line {
-                    Ok(s) => info.out_buf.push_back(s),
-                    Err(e) => info.out_buf.push_back(e.to_string()),
-                };
+        // Put both pipes in non-blocking mode so the single wait loop can drain
+        // whatever bytes are ready without ever parking on a read; this replaces
+        // the pair of per-process reader threads.
+        set_nonblocking(stdout.as_raw_fd())?;
+        set_nonblocking(stderr.as_raw_fd())?;
+
+        live_children().lock().unwrap().insert(child.id());
+
+        Ok(ProcessWithBuffer {
+            child,
+            info,
+            stdout: Some(stdout),
+            stderr: Some(stderr),
+            out_acc: Vec::new(),
+            err_acc: Vec::new(),
+            logger,
+            stage,
+            task_id,
+        })
+    }
+
+    /// Wait for the child, killing it if it outlives `timeout`.
+    ///
+    /// A single loop both advances the wait and pumps output: each tick drains
+    /// whatever is ready on the non-blocking pipes into `info`, then checks
+    /// `try_wait` and the deadline. On expiry the child is killed and reaped. A
+    /// final drain after exit flushes any buffered trailing output, so the
+    /// buffers in `info` are complete by the time the caller inspects them. The
+    /// `WaitOutcome` distinguishes a normal exit, a deadline timeout, and a
+    /// shutdown-initiated kill.
+    pub fn wait_with_timeout(&mut self, timeout: Duration) -> std::io::Result<WaitOutcome> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            self.pump();
+            if let Some(status) = self.child.try_wait()? {
+                self.pump();
+                self.flush_partial();
+                live_children().lock().unwrap().remove(&self.child.id());
+                return Ok(WaitOutcome::Exited(status));
             }
-        });
+            // A shutdown request is also a reason to stop waiting: kill the child
+            // (the main loop may already have SIGTERM'd it), reap it and bail. Distinguish
+            // the two stop reasons so a Ctrl-C isn't misreported as a timeout.
+            let timed_out = Instant::now() >= deadline;
+            if timed_out || SHUTDOWN.load(Ordering::SeqCst) {
+                let _ = self.child.kill();
+                self.child.wait()?;
+                self.pump();
+                self.flush_partial();
+                live_children().lock().unwrap().remove(&self.child.id());
+                return Ok(if timed_out { WaitOutcome::TimedOut } else { WaitOutcome::Interrupted });
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
 
-        let info2 = info.clone();
-        thread::spawn(move || {
-            for line in BufReader::new(stderr).lines() {
-                let mut info = info2.lock().unwrap();
-                match line {
-                    Ok(s) => info.err_buf.push(s),
-                    Err(e) => info.err_buf.push(e.to_string()),
-                };
+    /// Drain whatever bytes are currently available on both pipes, splitting on
+    /// newlines into `out_buf`/`err_buf`. The trailing partial line stays in the
+    /// accumulator until its newline arrives. The `info` mutex is taken once per
+    /// pump rather than once per line.
+    fn pump(&mut self) {
+        if let Some(stdout) = self.stdout.as_mut() {
+            drain_available(stdout, &mut self.out_acc);
+        }
+        if let Some(stderr) = self.stderr.as_mut() {
+            drain_available(stderr, &mut self.err_acc);
+        }
+
+        let out_lines = take_lines(&mut self.out_acc);
+        let err_lines = take_lines(&mut self.err_acc);
+        if out_lines.is_empty() && err_lines.is_empty() {
+            return;
+        }
+        if let Some(logger) = self.logger.as_ref() {
+            let mut log = logger.lock().unwrap();
+            for line in &out_lines {
+                log.write_line(self.stage, line);
             }
-        });
+            for line in &err_lines {
+                log.write_line(self.stage, line);
+            }
+        }
+        // Only build per-line events (and clone the string) when a sink is
+        // actually listening for them; otherwise this is pure waste per line.
+        if line_events_enabled() {
+            for line in out_lines.iter().chain(err_lines.iter()) {
+                emit_status(StatusEvent::LineEmitted { task_id: self.task_id, stage: self.stage, line: line.clone() });
+            }
+        }
+        let mut info = self.info.lock().unwrap();
+        for line in out_lines {
+            info.out_buf.push_back(line);
+        }
+        for line in err_lines {
+            info.err_buf.push(line);
+        }
+    }
+
+    /// Flush any trailing bytes that never got a final newline, once the child
+    /// is gone and no more will arrive.
+    fn flush_partial(&mut self) {
+        let out_partial = std::mem::take(&mut self.out_acc);
+        let err_partial = std::mem::take(&mut self.err_acc);
+        if out_partial.is_empty() && err_partial.is_empty() {
+            return;
+        }
+        let out_line = (!out_partial.is_empty()).then(|| String::from_utf8_lossy(&out_partial).into_owned());
+        let err_line = (!err_partial.is_empty()).then(|| String::from_utf8_lossy(&err_partial).into_owned());
+        if let Some(logger) = self.logger.as_ref() {
+            let mut log = logger.lock().unwrap();
+            if let Some(line) = out_line.as_ref() {
+                log.write_line(self.stage, line);
+            }
+            if let Some(line) = err_line.as_ref() {
+                log.write_line(self.stage, line);
+            }
+        }
+        let mut info = self.info.lock().unwrap();
+        if let Some(line) = out_line {
+            info.out_buf.push_back(line);
+        }
+        if let Some(line) = err_line {
+            info.err_buf.push(line);
+        }
+    }
+}
+
+/// Mark a file descriptor non-blocking so reads return `WouldBlock` instead of
+/// parking when no data is ready.
+fn set_nonblocking(fd: std::os::unix::io::RawFd) -> std::io::Result<()> {
+    // SAFETY: `fd` is a live pipe fd owned by the child handle for the duration
+    // of the call; fcntl with F_GETFL/F_SETFL is a standard, side-effect-free
+    // way to toggle O_NONBLOCK.
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL);
+        if flags < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Read everything currently available on `src` into `acc`, stopping at
+/// `WouldBlock` (nothing more ready) or EOF. Any other read error also ends the
+/// drain for this poll and is otherwise discarded.
+fn drain_available<R: Read>(src: &mut R, acc: &mut Vec<u8>) {
+    let mut buf = [0u8; 8192];
+    loop {
+        match src.read(&mut buf) {
+            Ok(0) => break, // EOF
+            Ok(n) => acc.extend_from_slice(&buf[..n]),
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => break,
+            Err(_) => break,
+        }
+    }
+}
+
+/// Split complete (newline-terminated) lines out of `acc`, returning them and
+/// leaving the trailing partial line in place.
+fn take_lines(acc: &mut Vec<u8>) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for i in 0..acc.len() {
+        if acc[i] == b'\n' {
+            let mut end = i;
+            if end > start && acc[end - 1] == b'\r' {
+                end -= 1;
+            }
+            lines.push(String::from_utf8_lossy(&acc[start..end]).into_owned());
+            start = i + 1;
+        }
+    }
+    if start > 0 {
+        acc.drain(..start);
+    }
+    lines
+}
+
+/// A task's full-output log file, written line-by-line by the forwarder with a
+/// stage prefix and timestamp. Rotates by size so verbose debug output across
+/// thousands of tasks can't fill the disk.
+pub struct TaskLogger {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
 
-        Ok(ProcessWithBuffer { child, info })
+impl TaskLogger {
+    /// Open (creating `LOG_DIR` and the file as needed) the log for `task_id`.
+    fn create(task_id: u32) -> std::io::Result<Self> {
+        let dir = Path::new(WORKING_DIR).join(LOG_DIR);
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("bb7_{:06}.log", task_id));
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(TaskLogger { path, file, size })
+    }
+
+    fn write_line(&mut self, stage: &str, line: &str) {
+        let entry = format!("[{}] [{stage}] {line}\n", now_epoch());
+        if self.file.write_all(entry.as_bytes()).is_err() {
+            return;
+        }
+        self.size += entry.len() as u64;
+        if self.size >= LOG_MAX_BYTES {
+            if let Err(e) = self.rotate() {
+                eprintln!("failed to rotate log {:?}: {e}", self.path);
+            }
+        }
+    }
+
+    /// Shift `.k` -> `.k+1` (dropping the oldest) and start a fresh base file.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+        for i in (1..LOG_KEEP).rev() {
+            let from = rotated_path(&self.path, i);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, i + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        self.file = OpenOptions::new().create(true).write(true).truncate(true).open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    fn flush(&mut self) {
+        let _ = self.file.flush();
     }
 }
 
+fn rotated_path(base: &Path, n: u32) -> PathBuf {
+    PathBuf::from(format!("{}.{}", base.display(), n))
+}
+
 fn make_sligocki_command(task_id: u32) -> Command {
     let mut sligocki_enum = Command::new(PYTHON_EXECUTABLE);
     if let Some(venv_name) = PYENV_VIRTUALENV {
@@ -228,17 +755,17 @@ fn make_sligocki_command(task_id: u32) -> Command {
     sligocki_enum
 }
 
-fn start_sligocki_task(task_id: u32, info: Arc<Mutex<TaskInfo>>) -> ProcessWithBuffer {
+fn start_sligocki_task(task_id: u32, info: Arc<Mutex<TaskInfo>>, logger: Option<Arc<Mutex<TaskLogger>>>) -> ProcessWithBuffer {
     let mut command = make_sligocki_command(task_id);
     {
         let mut info2 = info.lock().unwrap();
         info2.kind = TaskKind::SligockiEnum(task_id);
         info2.err_buf.clear();
     }
-    ProcessWithBuffer::new(&mut command, info).expect("failed to start job")
+    ProcessWithBuffer::new(&mut command, info, logger, "sligocki", task_id).expect("failed to start job")
 }
 
-fn start_mxdys_task(task_id: u32, info: Arc<Mutex<TaskInfo>>) -> ProcessWithBuffer {
+fn start_mxdys_task(task_id: u32, info: Arc<Mutex<TaskInfo>>, logger: Option<Arc<Mutex<TaskLogger>>>) -> ProcessWithBuffer {
     let mut command = Command::new(MXDYS_BB7_TM_EXECUTABLE);
     command.args(["enum", &format!("{}", task_id)])
         .current_dir(WORKING_DIR);
@@ -247,53 +774,479 @@ fn start_mxdys_task(task_id: u32, info: Arc<Mutex<TaskInfo>>) -> ProcessWithBuff
         info2.kind = TaskKind::MxdysEnum(task_id);
         info2.err_buf.clear();
     }
-    ProcessWithBuffer::new(&mut command, info).expect("failed to start job")
+    ProcessWithBuffer::new(&mut command, info, logger, "mxdys", task_id).expect("failed to start job")
 }
 
-fn combined_enumeration(task_id: u32, info: Arc<Mutex<TaskInfo>>) {
+fn combined_enumeration(task_id: u32, worker_id: usize, info: Arc<Mutex<TaskInfo>>, registry: Arc<Mutex<TaskRegistry>>, outcomes: Arc<SegQueue<TaskOutcome>>) {
+    let mut failed_stage: Option<&'static str> = None;
+    let mut worst_code: i32 = 0;
+    let mut timed_out = false;
+    let mut total_elapsed = Duration::ZERO;
+
+    let logger = if ENABLE_FILE_LOGGING {
+        match TaskLogger::create(task_id) {
+            Ok(l) => Some(Arc::new(Mutex::new(l))),
+            Err(e) => {
+                eprintln!("failed to open log for task {task_id}: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     {
-        let mut mxdys_job = start_mxdys_task(task_id, info.clone());
-        let exit_str = mxdys_job.child.wait().map_or("already exited".to_owned(), 
-            |r| if r.success() {
-                "success".to_owned()
-            } else {
-                format!("{}", r)
-            });
+        let mut mxdys_job = start_mxdys_task(task_id, info.clone(), logger.clone());
+        registry.lock().unwrap().mark_active(task_id, worker_id, mxdys_job.child.id());
+        emit_status(StatusEvent::TaskStarted { task_id, worker_id, stage: "mxdys" });
+        let timeout = task_timeout(&TaskKind::MxdysEnum(task_id));
+        let stage = classify_wait(mxdys_job.wait_with_timeout(timeout), timeout);
+        if !stage.ok {
+            failed_stage.get_or_insert("mxdys");
+            worst_code = worst_code.max(stage.code);
+            timed_out |= stage.timed_out;
+            if stage.timed_out {
+                mxdys_job.info.lock().unwrap().kind = TaskKind::TimedOut;
+            }
+        }
         let elapsed = {
             let info2 = mxdys_job.info.lock().unwrap();
             info2.start_time.elapsed()
         };
-        println!("mxdys enum {task_id} finished, {:.2?}, {exit_str}", elapsed);
+        total_elapsed += elapsed;
+        emit_status(StatusEvent::StageFinished { task_id, stage: "mxdys", exit_code: stage.code, timed_out: stage.timed_out, elapsed_secs: elapsed.as_secs_f64() });
+        println!("mxdys enum {task_id} finished, {:.2?}, {}", elapsed, stage.display);
+    }
+
+    // Interrupted between stages: hand the task back to the queue (pending) so
+    // the next launch reruns it cleanly, rather than recording it as done/failed.
+    if SHUTDOWN.load(Ordering::SeqCst) {
+        registry.lock().unwrap().mark_pending(task_id);
+        if let Some(logger) = logger {
+            logger.lock().unwrap().flush();
+        }
+        return;
     }
 
     {
-        let mut sligocki_job = start_sligocki_task(task_id, info.clone());
-        let exit_str = sligocki_job.child.wait().map_or("already exited".to_owned(), 
-            |r| if r.success() {
-                "success".to_owned()
-            } else {
-                format!("{}", r)
-            });
+        let mut sligocki_job = start_sligocki_task(task_id, info.clone(), logger.clone());
+        // Update the persisted active record to point at the sligocki PID so a
+        // crash during this stage leaves an accurate entry, not the dead mxdys PID.
+        registry.lock().unwrap().mark_active(task_id, worker_id, sligocki_job.child.id());
+        emit_status(StatusEvent::TaskStarted { task_id, worker_id, stage: "sligocki" });
+        let timeout = task_timeout(&TaskKind::SligockiEnum(task_id));
+        let stage = classify_wait(sligocki_job.wait_with_timeout(timeout), timeout);
+        if !stage.ok {
+            failed_stage.get_or_insert("sligocki");
+            worst_code = worst_code.max(stage.code);
+            timed_out |= stage.timed_out;
+            if stage.timed_out {
+                sligocki_job.info.lock().unwrap().kind = TaskKind::TimedOut;
+            }
+        }
         let elapsed = {
             let info2 = sligocki_job.info.lock().unwrap();
             info2.start_time.elapsed()
         };
-        println!("sligocki enum {task_id} finished, {:.2?}, {exit_str}", elapsed);
+        total_elapsed += elapsed;
+        emit_status(StatusEvent::StageFinished { task_id, stage: "sligocki", exit_code: stage.code, timed_out: stage.timed_out, elapsed_secs: elapsed.as_secs_f64() });
+        println!("sligocki enum {task_id} finished, {:.2?}, {}", elapsed, stage.display);
+    }
+
+    // A shutdown that landed mid-sligocki makes the wait return early; hand the
+    // task back as pending rather than recording the interrupted stage as a
+    // failure and polluting tasks.failed / the run summary.
+    if SHUTDOWN.load(Ordering::SeqCst) {
+        registry.lock().unwrap().mark_pending(task_id);
+        if let Some(logger) = logger {
+            logger.lock().unwrap().flush();
+        }
+        return;
     }
 
+    let stage_failed = failed_stage.is_some();
+
     {
         let mut info2 = info.lock().unwrap();
         info2.kind = TaskKind::MoveFile;
     }
+    // Both stages are done; flush and drop the log file before the move step.
+    if let Some(logger) = logger {
+        logger.lock().unwrap().flush();
+    }
     let fname = format!("bb7_{:06}.out.pb", task_id);
     let path_from = Path::new(WORKING_DIR).join(&fname);
     let path_to = Path::new(COMPLETED_DIR).join(&fname);
     if path_from.exists() {
         match fs::rename(&path_from, &path_to) {
             Err(_) => eprintln!("failed to move file: {path_from:?} to {path_to:?}"),
-            _ => (),
+            Ok(()) => emit_status(StatusEvent::TaskMoved {
+                task_id,
+                from: path_from.display().to_string(),
+                to: path_to.display().to_string(),
+            }),
         }
     } else {
         eprintln!("Could not find {path_from:?}");
     }
+
+    {
+        let mut reg = registry.lock().unwrap();
+        if stage_failed {
+            reg.mark_failed(task_id);
+        } else {
+            reg.mark_completed(task_id);
+        }
+    }
+
+    let err_tail = if stage_failed {
+        let info2 = info.lock().unwrap();
+        let n = info2.err_buf.len();
+        info2.err_buf[n.saturating_sub(ERR_TAIL_LINES)..].to_vec()
+    } else {
+        Vec::new()
+    };
+    outcomes.push(TaskOutcome {
+        task_id,
+        failed_stage,
+        exit_code: worst_code,
+        timed_out,
+        elapsed: total_elapsed,
+        err_tail,
+    });
+}
+
+/// How many trailing `err_buf` lines to keep for a failed task's triage blurb.
+const ERR_TAIL_LINES: usize = 10;
+
+/// Why `wait_with_timeout` stopped waiting on a child.
+pub enum WaitOutcome {
+    Exited(ExitStatus),
+    TimedOut,
+    Interrupted,
+}
+
+/// Result of waiting on one stage, normalized for the run summary.
+struct StageResult {
+    ok: bool,
+    code: i32,
+    timed_out: bool,
+    display: String,
+}
+
+fn classify_wait(res: std::io::Result<WaitOutcome>, timeout: Duration) -> StageResult {
+    match res {
+        Ok(WaitOutcome::Exited(r)) if r.success() => StageResult { ok: true, code: 0, timed_out: false, display: "success".to_owned() },
+        Ok(WaitOutcome::Exited(r)) => StageResult { ok: false, code: r.code().unwrap_or(1), timed_out: false, display: format!("{}", r) },
+        // 124 is the conventional exit code for a process killed on timeout (cf. coreutils `timeout`).
+        Ok(WaitOutcome::TimedOut) => StageResult { ok: false, code: 124, timed_out: true, display: format!("timed out after {:.2?}", timeout) },
+        // Killed by the shutdown handler, not a genuine failure; the caller
+        // requeues the task as pending instead of recording a timeout.
+        Ok(WaitOutcome::Interrupted) => StageResult { ok: false, code: 130, timed_out: false, display: "interrupted (shutdown)".to_owned() },
+        Err(e) => StageResult { ok: false, code: 1, timed_out: false, display: format!("wait failed: {e}") },
+    }
+}
+
+/// A finished task's outcome, accumulated across workers for the final report.
+pub struct TaskOutcome {
+    task_id: u32,
+    failed_stage: Option<&'static str>,
+    exit_code: i32,
+    timed_out: bool,
+    elapsed: Duration,
+    err_tail: Vec<String>,
+}
+
+/// Print a merged report of every task outcome and return the worst exit code
+/// seen, so the binary can propagate a nonzero status when anything failed.
+fn merge_exitcodes(outcomes: &Arc<SegQueue<TaskOutcome>>) -> i32 {
+    let mut total = 0usize;
+    let mut succeeded = 0usize;
+    let mut timed_out = 0usize;
+    let mut failures: Vec<TaskOutcome> = Vec::new();
+    let mut worst = 0i32;
+
+    while let Some(o) = outcomes.pop() {
+        total += 1;
+        worst = worst.max(o.exit_code);
+        if o.timed_out {
+            timed_out += 1;
+        }
+        if o.failed_stage.is_none() {
+            succeeded += 1;
+        } else {
+            failures.push(o);
+        }
+    }
+
+    println!("==== run summary ====");
+    println!("total: {total}, succeeded: {succeeded}, failed: {}, timed out: {timed_out}", failures.len());
+    println!("worst exit code: {worst}");
+    if !failures.is_empty() {
+        failures.sort_by_key(|o| o.task_id);
+        println!("failing tasks:");
+        for o in &failures {
+            let stage = o.failed_stage.unwrap_or("?");
+            println!("  [{:06}] stage={stage} code={} timed_out={} elapsed={:.2?}",
+                o.task_id, o.exit_code, o.timed_out, o.elapsed);
+            for line in &o.err_tail {
+                println!("      {line}");
+            }
+        }
+    }
+    emit_status(StatusEvent::RunComplete {
+        total,
+        succeeded,
+        failed: failures.len(),
+        timed_out,
+        worst_code: worst,
+    });
+    worst
+}
+
+/// How much of the status stream to emit.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Debug,
+}
+
+/// Where the status stream goes. A JSON-lines file, a bound Unix domain socket,
+/// or nowhere.
+pub enum StatusSinkConfig {
+    Disabled,
+    File(&'static str),
+    Socket(&'static str),
+}
+
+/// A serde-serializable status event. Serialized as one JSON object per line.
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum StatusEvent {
+    TaskStarted { task_id: u32, worker_id: usize, stage: &'static str },
+    LineEmitted { task_id: u32, stage: &'static str, line: String },
+    StageFinished { task_id: u32, stage: &'static str, exit_code: i32, timed_out: bool, elapsed_secs: f64 },
+    TaskMoved { task_id: u32, from: String, to: String },
+    Snapshot { workers: Vec<WorkerSnapshot> },
+    RunComplete { total: usize, succeeded: usize, failed: usize, timed_out: usize, worst_code: i32 },
+}
+
+/// One worker's state in a periodic snapshot event.
+#[derive(Serialize)]
+pub struct WorkerSnapshot {
+    pub worker_id: usize,
+    pub kind: String,
+    pub elapsed_secs: f64,
+    pub last_line: String,
+}
+
+/// A pluggable newline-delimited-JSON sink. For sockets, a background thread
+/// accepts a single client so writes never block startup; until a client
+/// connects the events are simply dropped.
+pub struct StatusSink {
+    verbosity: Verbosity,
+    inner: StatusInner,
+}
+
+enum StatusInner {
+    File(Mutex<File>),
+    /// The single accepted client, filled in by a background accept thread.
+    Socket(Arc<Mutex<Option<UnixStream>>>),
+}
+
+impl StatusSink {
+    fn from_config() -> Option<StatusSink> {
+        match STATUS_SINK_CONFIG {
+            StatusSinkConfig::Disabled => None,
+            StatusSinkConfig::File(path) => {
+                match OpenOptions::new().create(true).write(true).truncate(true).open(path) {
+                    Ok(f) => Some(StatusSink { verbosity: STATUS_VERBOSITY, inner: StatusInner::File(Mutex::new(f)) }),
+                    Err(e) => {
+                        eprintln!("failed to open status file {path:?}: {e}");
+                        None
+                    }
+                }
+            }
+            StatusSinkConfig::Socket(path) => {
+                // Start fresh: a stale socket file makes `bind` fail with EADDRINUSE.
+                let _ = fs::remove_file(path);
+                match UnixListener::bind(path) {
+                    Ok(listener) => {
+                        let slot: Arc<Mutex<Option<UnixStream>>> = Arc::new(Mutex::new(None));
+                        let accept_slot = Arc::clone(&slot);
+                        thread::spawn(move || {
+                            if let Ok((stream, _)) = listener.accept() {
+                                *accept_slot.lock().unwrap() = Some(stream);
+                            }
+                        });
+                        Some(StatusSink { verbosity: STATUS_VERBOSITY, inner: StatusInner::Socket(slot) })
+                    }
+                    Err(e) => {
+                        eprintln!("failed to bind status socket {path:?}: {e}");
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    fn should_emit(&self, event: &StatusEvent) -> bool {
+        match event {
+            StatusEvent::RunComplete { .. } => true,
+            StatusEvent::LineEmitted { .. } => self.verbosity == Verbosity::Debug,
+            _ => self.verbosity != Verbosity::Quiet,
+        }
+    }
+
+    fn emit(&self, event: &StatusEvent) {
+        if !self.should_emit(event) {
+            return;
+        }
+        let Ok(json) = serde_json::to_string(event) else { return };
+        match &self.inner {
+            StatusInner::File(m) => {
+                let mut f = m.lock().unwrap();
+                let _ = writeln!(f, "{json}");
+            }
+            StatusInner::Socket(slot) => {
+                if let Some(stream) = slot.lock().unwrap().as_mut() {
+                    let _ = writeln!(stream, "{json}");
+                }
+            }
+        }
+    }
+}
+
+static STATUS_SINK: OnceLock<Option<StatusSink>> = OnceLock::new();
+
+fn status_sink() -> Option<&'static StatusSink> {
+    STATUS_SINK.get_or_init(StatusSink::from_config).as_ref()
+}
+
+/// Emit a status event to the configured sink, if any.
+fn emit_status(event: StatusEvent) {
+    if let Some(sink) = status_sink() {
+        sink.emit(&event);
+    }
+}
+
+/// Whether per-line `LineEmitted` events would actually be written, so callers
+/// can skip constructing them (and cloning their payload) when they wouldn't.
+fn line_events_enabled() -> bool {
+    status_sink().is_some_and(|s| s.verbosity == Verbosity::Debug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::sync::atomic::AtomicU32;
+
+    static TMP_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn tmp_path(tag: &str) -> PathBuf {
+        let n = TMP_COUNTER.fetch_add(1, Ordering::SeqCst);
+        std::env::temp_dir().join(format!("bb7_test_{}_{}_{tag}", std::process::id(), n))
+    }
+
+    #[test]
+    fn take_lines_splits_and_retains_partial() {
+        let mut acc = b"alpha\nbeta\ngamm".to_vec();
+        let lines = take_lines(&mut acc);
+        assert_eq!(lines, vec!["alpha".to_owned(), "beta".to_owned()]);
+        assert_eq!(acc, b"gamm");
+    }
+
+    #[test]
+    fn take_lines_strips_crlf() {
+        let mut acc = b"one\r\ntwo\r\n".to_vec();
+        let lines = take_lines(&mut acc);
+        assert_eq!(lines, vec!["one".to_owned(), "two".to_owned()]);
+        assert!(acc.is_empty());
+    }
+
+    #[test]
+    fn take_lines_without_newline_keeps_everything() {
+        let mut acc = b"no newline yet".to_vec();
+        let lines = take_lines(&mut acc);
+        assert!(lines.is_empty());
+        assert_eq!(acc, b"no newline yet");
+    }
+
+    #[test]
+    fn ids_round_trip_through_file() {
+        let path = tmp_path("ids");
+        let mut ids = HashSet::new();
+        ids.insert(7u32);
+        ids.insert(1u32);
+        ids.insert(42u32);
+        fs::write(&path, ids_to_string(&ids)).unwrap();
+        let back = read_ids(&path);
+        assert_eq!(ids, back);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn ids_to_string_is_sorted() {
+        let mut ids = HashSet::new();
+        ids.insert(3u32);
+        ids.insert(1u32);
+        ids.insert(2u32);
+        assert_eq!(ids_to_string(&ids), "1\n2\n3\n");
+    }
+
+    #[test]
+    fn read_active_parses_records() {
+        let path = tmp_path("active");
+        fs::write(&path, "5 2 1234 1600000000\n6 0 5678 1600000001\n").unwrap();
+        let map = read_active(&path);
+        assert_eq!(map.len(), 2);
+        let e = &map[&5];
+        assert_eq!(e.worker_id, 2);
+        assert_eq!(e.pid, 1234);
+        assert_eq!(e.start_epoch, 1600000000);
+        assert_eq!(map[&6].pid, 5678);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn read_active_skips_malformed_lines() {
+        let path = tmp_path("active_bad");
+        fs::write(&path, "9 1 100 1\ngarbage line\n10 2 200\n").unwrap();
+        let map = read_active(&path);
+        assert_eq!(map.len(), 1);
+        assert!(map.contains_key(&9));
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn rotated_path_appends_generation() {
+        let base = Path::new("logs/bb7_000001.log");
+        assert_eq!(rotated_path(base, 1), PathBuf::from("logs/bb7_000001.log.1"));
+        assert_eq!(rotated_path(base, 3), PathBuf::from("logs/bb7_000001.log.3"));
+    }
+
+    #[test]
+    fn classify_wait_maps_outcomes() {
+        let t = Duration::from_secs(1);
+
+        let success = classify_wait(Ok(WaitOutcome::Exited(ExitStatus::from_raw(0))), t);
+        assert!(success.ok);
+        assert_eq!(success.code, 0);
+
+        let failed = classify_wait(Ok(WaitOutcome::Exited(ExitStatus::from_raw(1 << 8))), t);
+        assert!(!failed.ok);
+        assert_eq!(failed.code, 1);
+
+        let timed = classify_wait(Ok(WaitOutcome::TimedOut), t);
+        assert!(!timed.ok);
+        assert!(timed.timed_out);
+        assert_eq!(timed.code, 124);
+
+        let interrupted = classify_wait(Ok(WaitOutcome::Interrupted), t);
+        assert!(!interrupted.ok);
+        assert!(!interrupted.timed_out);
+        assert_eq!(interrupted.code, 130);
+    }
 }